@@ -0,0 +1,27 @@
+// benches/processor_benchmark.rs
+/*
+ * Criterion throughput benchmark for NodeRunnerProcessor::process
+ */
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use noderunner::NodeRunnerProcessor;
+
+const PAYLOAD_SIZES: &[usize] = &[64, 1024, 64 * 1024, 1024 * 1024];
+
+fn bench_process(c: &mut Criterion) {
+    let mut group = c.benchmark_group("process");
+
+    for &size in PAYLOAD_SIZES {
+        let payload = "x".repeat(size);
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &payload, |b, payload| {
+            let mut processor = NodeRunnerProcessor::new(false);
+            b.iter(|| processor.process(payload).unwrap());
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_process);
+criterion_main!(benches);
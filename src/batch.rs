@@ -0,0 +1,85 @@
+// src/batch.rs
+/*
+ * Asynchronous, concurrently processed batches of inputs
+ */
+
+use crate::{lossy, NodeRunnerProcessor, ProcessResult, Result};
+use futures::executor::ThreadPool;
+use futures::future::join_all;
+use futures::task::SpawnExt;
+
+/// Maximum number of inputs `process_batch` processes concurrently
+const DEFAULT_CONCURRENCY: usize = 8;
+
+/// Processes each path in `inputs` concurrently, bounded by
+/// `DEFAULT_CONCURRENCY` so one slow input can't block the others
+/// # `process_one` is blocking (file I/O plus CPU-bound processing), so
+/// # each call is spawned onto a `ThreadPool` with `DEFAULT_CONCURRENCY`
+/// # worker threads rather than polled as a future with no await points —
+/// # polling synchronous work on a single-threaded executor would just
+/// # serialize it, defeating the point of running inputs concurrently
+/// # Arguments:
+/// * `verbose`: Whether each per-file processor logs verbosely
+/// * `inputs`: Paths to process; the returned summary preserves this order
+/// # Returns a summary `ProcessResult` whose `data` holds the per-file
+/// # outcome and the overall success/failure count
+pub async fn process_batch(verbose: bool, inputs: &[String]) -> Result<ProcessResult> {
+    let pool = ThreadPool::builder()
+        .pool_size(DEFAULT_CONCURRENCY)
+        .create()
+        .map_err(|err| format!("failed to create thread pool: {}", err))?;
+
+    let handles: Vec<_> = inputs
+        .iter()
+        .cloned()
+        .map(|path| {
+            // `Box<dyn Error>` isn't `Send`, so carry failures across the
+            // thread-pool boundary as a `String` and let the aggregation
+            // below turn them back into the JSON shape callers expect.
+            pool.spawn_with_handle(async move {
+                let outcome = process_one(verbose, &path).map_err(|err| err.to_string());
+                (path, outcome)
+            })
+        })
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|err| format!("failed to spawn task on thread pool: {}", err))?;
+
+    let outcomes: Vec<(String, std::result::Result<ProcessResult, String>)> =
+        join_all(handles).await;
+
+    let mut succeeded = 0usize;
+    let mut failed = 0usize;
+    let files: Vec<serde_json::Value> = outcomes
+        .iter()
+        .map(|(path, outcome)| match outcome {
+            Ok(result) => {
+                succeeded += 1;
+                serde_json::json!({ "path": path, "success": true, "result": result })
+            }
+            Err(err) => {
+                failed += 1;
+                serde_json::json!({ "path": path, "success": false, "error": err })
+            }
+        })
+        .collect();
+
+    Ok(ProcessResult {
+        success: failed == 0,
+        message: format!("Processed {} of {} input(s)", succeeded, outcomes.len()),
+        data: Some(serde_json::json!({
+            "total": outcomes.len(),
+            "succeeded": succeeded,
+            "failed": failed,
+            "files": files
+        })),
+    })
+}
+
+/// Reads and processes a single input path with its own `NodeRunnerProcessor`
+/// # Synchronous and blocking by design — callers are expected to run this
+/// # on a thread pool rather than poll it as a non-blocking future
+fn process_one(verbose: bool, path: &str) -> Result<ProcessResult> {
+    let data = lossy::read_lossy_file(path)?;
+    let mut processor = NodeRunnerProcessor::new(verbose);
+    processor.process(&data)
+}
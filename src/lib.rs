@@ -3,10 +3,26 @@
  * Core library for NodeRunner
  */
 
-use log::{info, error, debug};
+use log::{info, debug};
 use serde::{Serialize, Deserialize};
+use std::collections::HashMap;
+use std::fmt;
 use std::fs;
-use std::path::Path;
+use std::io::{self, Read};
+use std::time::{Duration, Instant};
+
+mod batch;
+mod lossy;
+pub mod maelstrom;
+pub mod runner;
+mod server;
+pub mod transport;
+
+pub use batch::process_batch;
+pub use lossy::{parse_lossy, read_lossy_file, LossyString};
+pub use maelstrom::Handler;
+pub use runner::{run_node, Node};
+pub use server::{serve, Framing};
 
 /// Custom result type with error handling
 /// # Type alias for a Result type with a boxed error
@@ -26,12 +42,37 @@ pub struct ProcessResult {
 
 /// NodeRunner processor
 /// # Struct representing the processor with verbosity and processed count
-#[derive(Debug)]
 pub struct NodeRunnerProcessor {
     /// Whether to print debug information
     pub verbose: bool,
     /// Number of items processed
     pub processed_count: usize,
+    /// This node's id, as assigned by a Maelstrom `init` message
+    pub node_id: Option<String>,
+    /// The full cluster membership, as assigned by a Maelstrom `init` message
+    pub node_ids: Vec<String>,
+    /// Cumulative bytes passed to `process`, used to report `bytes_per_sec`
+    pub bytes_processed: u64,
+    /// Cumulative time spent inside `process`, used to report `items_per_sec`
+    /// and `bytes_per_sec`
+    pub total_duration: Duration,
+    next_msg_id: u64,
+    handlers: HashMap<String, Handler>,
+}
+
+impl fmt::Debug for NodeRunnerProcessor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NodeRunnerProcessor")
+            .field("verbose", &self.verbose)
+            .field("processed_count", &self.processed_count)
+            .field("node_id", &self.node_id)
+            .field("node_ids", &self.node_ids)
+            .field("bytes_processed", &self.bytes_processed)
+            .field("total_duration", &self.total_duration)
+            .field("next_msg_id", &self.next_msg_id)
+            .field("handlers", &self.handlers.keys().collect::<Vec<_>>())
+            .finish()
+    }
 }
 
 impl NodeRunnerProcessor {
@@ -41,6 +82,46 @@ impl NodeRunnerProcessor {
         Self {
             verbose,
             processed_count: 0,
+            node_id: None,
+            node_ids: Vec::new(),
+            bytes_processed: 0,
+            total_duration: Duration::ZERO,
+            next_msg_id: 0,
+            handlers: HashMap::new(),
+        }
+    }
+
+    /// Registers `handler` to be invoked for messages whose `body.type`
+    /// equals `message_type`
+    pub fn register_handler(&mut self, message_type: &str, handler: Handler) {
+        self.handlers.insert(message_type.to_string(), handler);
+    }
+
+    /// Returns the next outgoing Maelstrom `msg_id`, incrementing the counter
+    pub fn next_msg_id(&mut self) -> u64 {
+        self.next_msg_id += 1;
+        self.next_msg_id
+    }
+
+    /// Dispatches `message` to the handler registered for its `body.type`
+    /// # Returns an error-shaped `Body` if no handler is registered
+    pub fn dispatch_message(&mut self, message: &maelstrom::Message) -> Result<maelstrom::Body> {
+        let message_type = message.body.typ.clone();
+        match self.handlers.remove(&message_type) {
+            Some(mut handler) => {
+                let result = handler(self, message);
+                self.handlers.insert(message_type, handler);
+                result
+            }
+            None => Ok(maelstrom::Body {
+                typ: "error".to_string(),
+                msg_id: None,
+                in_reply_to: None,
+                extra: serde_json::json!({
+                    "code": 10,
+                    "text": format!("unsupported message type: {}", message_type)
+                }),
+            }),
         }
     }
 
@@ -54,9 +135,11 @@ impl NodeRunnerProcessor {
             debug!("Processing data of length: {}", data.len());
         }
 
+        let started_at = Instant::now();
+
         // Simulate processing
         self.processed_count += 1;
-        
+
         let result = ProcessResult {
             success: true,
             message: format!("Successfully processed item #{}", self.processed_count),
@@ -67,15 +150,65 @@ impl NodeRunnerProcessor {
             })),
         };
 
+        self.bytes_processed += data.len() as u64;
+        self.total_duration += started_at.elapsed();
+
         Ok(result)
     }
 
     /// Returns statistics about the processor
-    /// # Returns statistics as a JSON value
+    /// # Returns statistics as a JSON value, including throughput derived
+    /// # from cumulative bytes processed and time spent in `process`
     pub fn get_stats(&self) -> serde_json::Value {
+        let seconds = self.total_duration.as_secs_f64();
+        let (items_per_sec, bytes_per_sec) = if seconds > 0.0 {
+            (
+                self.processed_count as f64 / seconds,
+                self.bytes_processed as f64 / seconds,
+            )
+        } else {
+            (0.0, 0.0)
+        };
+
         serde_json::json!({
             "processed_count": self.processed_count,
-            "verbose": self.verbose
+            "verbose": self.verbose,
+            "bytes_processed": self.bytes_processed,
+            "items_per_sec": items_per_sec,
+            "bytes_per_sec": bytes_per_sec
         })
     }
+}
+
+/// Runs NodeRunner in single-shot mode
+/// # Entry point invoked by `main` after CLI args are parsed
+/// # Arguments:
+/// * `verbose`: Whether to enable verbose logging
+/// * `input`: Optional path to read input from; falls back to stdin
+/// * `output`: Optional path to write the result to; falls back to stdout
+pub fn run(verbose: bool, input: Option<String>, output: Option<String>) -> Result<()> {
+    let mut processor = NodeRunnerProcessor::new(verbose);
+
+    let data = match &input {
+        Some(path) => lossy::read_lossy_file(path)?,
+        None => {
+            let mut buf = Vec::new();
+            io::stdin().read_to_end(&mut buf)?;
+            String::from_utf8_lossy(&buf).into_owned()
+        }
+    };
+
+    let result = processor.process(&data)?;
+    let rendered = serde_json::to_string_pretty(&result)?;
+
+    match output {
+        Some(path) => fs::write(path, rendered)?,
+        None => println!("{}", rendered),
+    }
+
+    if verbose {
+        info!("Processed {} item(s)", processor.processed_count);
+    }
+
+    Ok(())
 }
\ No newline at end of file
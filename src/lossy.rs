@@ -0,0 +1,170 @@
+// src/lossy.rs
+/*
+ * Lossy UTF-8 / lone-surrogate tolerant string handling
+ */
+
+use crate::Result;
+use serde::Deserialize;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// A `String` built from JSON text that has already been through
+/// [`sanitize_lone_surrogates`] — by the time serde hands us a `&str` any
+/// unpaired surrogate escape has already become U+FFFD, since a lone
+/// surrogate cannot be represented in a Rust `String` in the first place.
+/// # Newtype marking a value as having been produced via the lossy
+/// # parsing path, so callers know it can't contain a deserialization
+/// # error from malformed escapes
+#[derive(Debug, Clone, PartialEq, Eq, Default, Deserialize)]
+#[serde(transparent)]
+pub struct LossyString(pub String);
+
+impl LossyString {
+    /// Returns the underlying string slice
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<LossyString> for String {
+    fn from(value: LossyString) -> Self {
+        value.0
+    }
+}
+
+impl fmt::Display for LossyString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Reads `path` as a string, never failing on bytes that aren't valid UTF-8
+/// # Invalid byte sequences are replaced with U+FFFD, matching the behavior
+/// # of `String::from_utf8_lossy`
+pub fn read_lossy_file<P: AsRef<Path>>(path: P) -> Result<String> {
+    let bytes = fs::read(path)?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Parses `text` as JSON, first repairing unpaired `\uXXXX` surrogate escapes
+/// # Any high surrogate (U+D800-U+DBFF) not immediately followed by a valid
+/// # low surrogate (U+DC00-U+DFFF) escape is rewritten to `�` before
+/// # handing the text to `serde_json`, so malformed escapes degrade to the
+/// # replacement character instead of an `Err`
+pub fn parse_lossy(text: &str) -> Result<serde_json::Value> {
+    Ok(serde_json::from_str(&sanitize_lone_surrogates(text))?)
+}
+
+/// Rewrites unpaired `\uXXXX` surrogate escapes in raw JSON text to `�`
+/// # Shared by `parse_lossy` and callers that deserialize into a concrete
+/// # type rather than a generic `Value`
+/// # Walks the text one escape sequence at a time (rather than scanning for
+/// # `\u` anywhere) so that an escaped backslash (`\\`) is never mistaken
+/// # for the start of a `\u` escape — e.g. the literal text `\\uD800x`
+/// # (an escaped `\` followed by the characters `uD800x`) is left untouched
+pub(crate) fn sanitize_lone_surrogates(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        if chars[i] != '\\' {
+            out.push(chars[i]);
+            i += 1;
+            continue;
+        }
+
+        if let Some(high) = parse_unicode_escape(&chars, i) {
+            if is_high_surrogate(high) {
+                match parse_unicode_escape(&chars, i + 6) {
+                    Some(low) if is_low_surrogate(low) => {
+                        out.push_str(&format!("\\u{:04x}\\u{:04x}", high, low));
+                        i += 12;
+                    }
+                    _ => {
+                        out.push('\u{FFFD}');
+                        i += 6;
+                    }
+                }
+            } else if is_low_surrogate(high) {
+                // A lone low surrogate with no preceding high surrogate.
+                out.push('\u{FFFD}');
+                i += 6;
+            } else {
+                out.push_str(&format!("\\u{:04x}", high));
+                i += 6;
+            }
+            continue;
+        }
+
+        // Any other escape (`\\`, `\"`, `\n`, ...): consume the backslash
+        // together with the character it escapes so that character is
+        // never reconsidered as the start of a new escape sequence.
+        out.push('\\');
+        i += 1;
+        if let Some(&next) = chars.get(i) {
+            out.push(next);
+            i += 1;
+        }
+    }
+
+    out
+}
+
+/// Parses a `\uXXXX` escape starting at `chars[start]`, if one is present
+fn parse_unicode_escape(chars: &[char], start: usize) -> Option<u32> {
+    if chars.get(start) != Some(&'\\') || chars.get(start + 1) != Some(&'u') {
+        return None;
+    }
+    let hex: String = chars.get(start + 2..start + 6)?.iter().collect();
+    u32::from_str_radix(&hex, 16).ok()
+}
+
+fn is_high_surrogate(code_point: u32) -> bool {
+    (0xD800..=0xDBFF).contains(&code_point)
+}
+
+fn is_low_surrogate(code_point: u32) -> bool {
+    (0xDC00..=0xDFFF).contains(&code_point)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lone_high_surrogate_becomes_replacement_char() {
+        assert_eq!(sanitize_lone_surrogates(r"\uD800"), r"�");
+    }
+
+    #[test]
+    fn lone_low_surrogate_becomes_replacement_char() {
+        assert_eq!(sanitize_lone_surrogates(r"\uDC00"), r"�");
+    }
+
+    #[test]
+    fn valid_surrogate_pair_is_kept_as_lowercase_escapes() {
+        let escaped = "\\uD800\\uDC00";
+        assert_eq!(sanitize_lone_surrogates(escaped), "\\ud800\\udc00");
+    }
+
+    #[test]
+    fn escaped_backslash_is_not_mistaken_for_a_unicode_escape() {
+        // `\\uD800x` is an escaped backslash followed by the literal
+        // characters `uD800x`, not a `\uD800` escape. It must survive
+        // untouched.
+        assert_eq!(sanitize_lone_surrogates(r"\\uD800x"), r"\\uD800x");
+    }
+
+    #[test]
+    fn other_escapes_pass_through_unchanged() {
+        assert_eq!(sanitize_lone_surrogates(r#"\"quoted\" and \n"#), r#"\"quoted\" and \n"#);
+    }
+
+    #[test]
+    fn parse_lossy_repairs_malformed_json_text() {
+        let value = parse_lossy(r#"{"text": "bad: \uD800"}"#).unwrap();
+        assert_eq!(value["text"], "bad: \u{FFFD}");
+    }
+}
@@ -0,0 +1,244 @@
+// src/maelstrom.rs
+/*
+ * Maelstrom-style message-passing node handler registry
+ */
+
+use crate::{NodeRunnerProcessor, Result};
+use serde::{Deserialize, Serialize};
+use std::io::{self, BufRead, Write};
+
+/// A Maelstrom-protocol message exchanged between nodes
+/// # Struct mirroring the `{src, dest, body}` envelope Maelstrom workloads use
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Message {
+    pub src: String,
+    pub dest: String,
+    pub body: Body,
+}
+
+/// The payload carried by a `Message`
+/// # `msg_id`/`in_reply_to` are managed by `reply`; any other fields a
+/// # handler needs (e.g. `echo`'s text) live in `extra`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Body {
+    #[serde(rename = "type")]
+    pub typ: String,
+    pub msg_id: Option<u64>,
+    pub in_reply_to: Option<u64>,
+    #[serde(flatten)]
+    pub extra: serde_json::Value,
+}
+
+/// A handler for one `body.type`, given the processor and the original
+/// message, returning the reply `Body`; `reply` fills in its `msg_id` and
+/// `in_reply_to`
+pub type Handler =
+    Box<dyn FnMut(&mut NodeRunnerProcessor, &Message) -> Result<Body> + Send>;
+
+/// Builds the reply envelope for `original`: swaps `src`/`dest`, stamps
+/// `in_reply_to` with the original `msg_id`, and assigns `msg_id` the next
+/// outgoing id from `processor`
+pub fn reply(processor: &mut NodeRunnerProcessor, original: &Message, mut body: Body) -> Message {
+    body.in_reply_to = original.body.msg_id;
+    body.msg_id = Some(processor.next_msg_id());
+
+    Message {
+        src: original.dest.clone(),
+        dest: original.src.clone(),
+        body,
+    }
+}
+
+/// Registers the built-in `init` and `echo` handlers on `processor`
+/// # `init` records the assigned `node_id`/`node_ids`; `echo` replies with
+/// # the same `extra` payload it received
+pub fn register_defaults(processor: &mut NodeRunnerProcessor) {
+    processor.register_handler(
+        "init",
+        Box::new(|processor, message| {
+            let node_id = message
+                .body
+                .extra
+                .get("node_id")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string();
+            let node_ids = message
+                .body
+                .extra
+                .get("node_ids")
+                .and_then(|v| v.as_array())
+                .map(|ids| {
+                    ids.iter()
+                        .filter_map(|id| id.as_str().map(str::to_string))
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            processor.node_id = Some(node_id);
+            processor.node_ids = node_ids;
+
+            Ok(Body {
+                typ: "init_ok".to_string(),
+                msg_id: None,
+                in_reply_to: None,
+                extra: serde_json::json!({}),
+            })
+        }),
+    );
+
+    processor.register_handler(
+        "echo",
+        Box::new(|_processor, message| {
+            Ok(Body {
+                typ: "echo_ok".to_string(),
+                msg_id: None,
+                in_reply_to: None,
+                extra: message.body.extra.clone(),
+            })
+        }),
+    );
+}
+
+/// Reads Maelstrom messages from stdin and dispatches each to the handler
+/// registered for its `body.type`, writing the handler's reply to stdout
+/// # Folds every dispatched message into `processor.processed_count`
+pub fn serve(processor: &mut NodeRunnerProcessor) -> Result<()> {
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+
+    for line in stdin.lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let message: Message = serde_json::from_str(&line)?;
+        let body = processor.dispatch_message(&message)?;
+        let response = reply(processor, &message, body);
+
+        writeln!(out, "{}", serde_json::to_string(&response)?)?;
+        out.flush()?;
+
+        processor.processed_count += 1;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(src: &str, dest: &str, typ: &str, msg_id: Option<u64>) -> Message {
+        Message {
+            src: src.to_string(),
+            dest: dest.to_string(),
+            body: Body {
+                typ: typ.to_string(),
+                msg_id,
+                in_reply_to: None,
+                extra: serde_json::json!({}),
+            },
+        }
+    }
+
+    #[test]
+    fn reply_swaps_src_and_dest() {
+        let mut processor = NodeRunnerProcessor::new(false);
+        let original = message("c1", "n1", "echo", Some(1));
+
+        let response = reply(&mut processor, &original, Body {
+            typ: "echo_ok".to_string(),
+            msg_id: None,
+            in_reply_to: None,
+            extra: serde_json::json!({}),
+        });
+
+        assert_eq!(response.src, "n1");
+        assert_eq!(response.dest, "c1");
+    }
+
+    #[test]
+    fn reply_sets_in_reply_to_from_original_msg_id() {
+        let mut processor = NodeRunnerProcessor::new(false);
+        let original = message("c1", "n1", "echo", Some(42));
+
+        let response = reply(&mut processor, &original, Body {
+            typ: "echo_ok".to_string(),
+            msg_id: None,
+            in_reply_to: None,
+            extra: serde_json::json!({}),
+        });
+
+        assert_eq!(response.body.in_reply_to, Some(42));
+    }
+
+    #[test]
+    fn reply_assigns_fresh_incrementing_msg_ids() {
+        let mut processor = NodeRunnerProcessor::new(false);
+        let original = message("c1", "n1", "echo", Some(1));
+
+        let first = reply(&mut processor, &original, Body {
+            typ: "echo_ok".to_string(),
+            msg_id: None,
+            in_reply_to: None,
+            extra: serde_json::json!({}),
+        });
+        let second = reply(&mut processor, &original, Body {
+            typ: "echo_ok".to_string(),
+            msg_id: None,
+            in_reply_to: None,
+            extra: serde_json::json!({}),
+        });
+
+        assert_eq!(first.body.msg_id, Some(1));
+        assert_eq!(second.body.msg_id, Some(2));
+    }
+
+    #[test]
+    fn init_handler_records_node_id_and_node_ids() {
+        let mut processor = NodeRunnerProcessor::new(false);
+        register_defaults(&mut processor);
+
+        let init = Message {
+            src: "c1".to_string(),
+            dest: "n1".to_string(),
+            body: Body {
+                typ: "init".to_string(),
+                msg_id: Some(1),
+                in_reply_to: None,
+                extra: serde_json::json!({"node_id": "n1", "node_ids": ["n1", "n2"]}),
+            },
+        };
+
+        let body = processor.dispatch_message(&init).unwrap();
+
+        assert_eq!(body.typ, "init_ok");
+        assert_eq!(processor.node_id, Some("n1".to_string()));
+        assert_eq!(processor.node_ids, vec!["n1".to_string(), "n2".to_string()]);
+    }
+
+    #[test]
+    fn echo_handler_echoes_extra_payload() {
+        let mut processor = NodeRunnerProcessor::new(false);
+        register_defaults(&mut processor);
+
+        let echo = Message {
+            src: "c1".to_string(),
+            dest: "n1".to_string(),
+            body: Body {
+                typ: "echo".to_string(),
+                msg_id: Some(1),
+                in_reply_to: None,
+                extra: serde_json::json!({"echo": "hello"}),
+            },
+        };
+
+        let body = processor.dispatch_message(&echo).unwrap();
+
+        assert_eq!(body.typ, "echo_ok");
+        assert_eq!(body.extra, serde_json::json!({"echo": "hello"}));
+    }
+}
@@ -3,8 +3,8 @@
  * Main executable for NodeRunner
  */
 
-use clap::Parser;
-use noderunner::{Result, run};
+use clap::{Parser, ValueEnum};
+use noderunner::{maelstrom, process_batch, run, run_node, serve, Framing, NodeRunnerProcessor, Result};
 
 #[derive(Parser)]
 #[command(version, about = "NodeRunner - A Rust implementation")]
@@ -12,17 +12,88 @@ struct Cli {
     /// Enable verbose output
     #[arg(short, long)]
     verbose: bool,
-    
-    /// Input file path
+
+    /// Input file path; pass more than once (or combine with
+    /// `--input-glob`) to process several inputs concurrently
     #[arg(short, long)]
-    input: Option<String>,
-    
+    input: Vec<String>,
+
+    /// Glob pattern matched against the filesystem; matches are appended
+    /// to `--input`
+    #[arg(long)]
+    input_glob: Option<String>,
+
     /// Output file path
     #[arg(short, long)]
     output: Option<String>,
+
+    /// Run as a long-lived JSON-RPC server over stdin/stdout instead of
+    /// processing a single input
+    #[arg(long)]
+    serve: bool,
+
+    /// Message framing used by `--serve`: newline-delimited JSON, or
+    /// `Content-Length` framing like an LSP server
+    #[arg(long, value_enum, default_value_t = CliFraming::Lines)]
+    framing: CliFraming,
+
+    /// External command to spawn and supervise as a node
+    #[arg(long)]
+    node_command: Option<String>,
+
+    /// Arguments passed to `--node-command`
+    #[arg(long)]
+    node_args: Vec<String>,
+
+    /// Regex matched against the node's stdout lines to detect readiness;
+    /// its first capture group must parse as a `host:port` socket address
+    #[arg(long, default_value = r"listening on ([^\s]+)")]
+    node_ready_regex: String,
+
+    /// Run as a Maelstrom-protocol message-passing node over stdin/stdout
+    #[arg(long)]
+    maelstrom: bool,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum CliFraming {
+    Lines,
+    Lsp,
+}
+
+impl From<CliFraming> for Framing {
+    fn from(value: CliFraming) -> Self {
+        match value {
+            CliFraming::Lines => Framing::Lines,
+            CliFraming::Lsp => Framing::Lsp,
+        }
+    }
 }
 
 fn main() -> Result<()> {
-    let args = Cli::parse();
-    run(args.verbose, args.input, args.output)
+    let mut args = Cli::parse();
+    if let Some(pattern) = &args.input_glob {
+        for entry in glob::glob(pattern)? {
+            args.input.push(entry?.to_string_lossy().into_owned());
+        }
+    }
+
+    if args.maelstrom {
+        let mut processor = NodeRunnerProcessor::new(args.verbose);
+        maelstrom::register_defaults(&mut processor);
+        maelstrom::serve(&mut processor)
+    } else if let Some(command) = &args.node_command {
+        let mut processor = NodeRunnerProcessor::new(args.verbose);
+        let result = run_node(&mut processor, command, &args.node_args, &args.node_ready_regex)?;
+        println!("{}", serde_json::to_string_pretty(&result)?);
+        Ok(())
+    } else if args.serve {
+        serve(args.verbose, args.framing.into())
+    } else if args.input.len() > 1 {
+        let result = futures::executor::block_on(process_batch(args.verbose, &args.input))?;
+        println!("{}", serde_json::to_string_pretty(&result)?);
+        Ok(())
+    } else {
+        run(args.verbose, args.input.into_iter().next(), args.output)
+    }
 }
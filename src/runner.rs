@@ -0,0 +1,122 @@
+// src/runner.rs
+/*
+ * Child-process runner subsystem: spawns and supervises external nodes
+ */
+
+use crate::{NodeRunnerProcessor, ProcessResult, Result};
+use log::error;
+use regex::Regex;
+use std::io::{BufRead, BufReader, Read};
+use std::net::SocketAddr;
+use std::process::{Child, Command, Stdio};
+
+/// A supervised external process
+/// # Struct wrapping a spawned child, its command line, and whatever
+/// # stdout/stderr were captured while waiting for it to become ready
+pub struct Node {
+    child: Child,
+    command: String,
+    /// Raw stdout captured so far, kept as bytes since a misbehaving child
+    /// may write output that isn't valid UTF-8
+    pub stdout: Vec<u8>,
+    /// Raw stderr captured so far, kept as bytes for the same reason
+    pub stderr: Vec<u8>,
+}
+
+impl Node {
+    /// Spawns `command` with `args`, piping its stdout and stderr
+    pub fn spawn(command: &str, args: &[String]) -> Result<Self> {
+        let child = Command::new(command)
+            .args(args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        Ok(Self {
+            child,
+            command: command.to_string(),
+            stdout: Vec::new(),
+            stderr: Vec::new(),
+        })
+    }
+
+    /// Spawns `command` and streams its stdout line-by-line until a line
+    /// matches `ready_pattern`, returning the supervised `Node` along with
+    /// the `host:port` captured by the pattern's first capture group
+    /// # Returns the `Node` so the caller holds the only handle to the
+    /// # child and can stop it; dropping the return value would otherwise
+    /// # leak the spawned process
+    /// # If the child exits before a line matches, returns an error that
+    /// # includes everything captured on stdout and stderr
+    pub fn spawn_and_wait_ready(
+        command: &str,
+        args: &[String],
+        ready_pattern: &str,
+    ) -> Result<(Self, SocketAddr)> {
+        let pattern = Regex::new(ready_pattern)?;
+        let mut node = Self::spawn(command, args)?;
+
+        let stdout = node.child.stdout.take().ok_or("child has no stdout")?;
+        let mut reader = BufReader::new(stdout);
+        let mut line = Vec::new();
+
+        loop {
+            line.clear();
+            if reader.read_until(b'\n', &mut line)? == 0 {
+                break;
+            }
+            node.stdout.extend_from_slice(&line);
+
+            let text = String::from_utf8_lossy(&line);
+            if let Some(captures) = pattern.captures(&text) {
+                let addr = captures.name("addr").or_else(|| captures.get(1));
+                if let Some(addr) = addr.and_then(|m| m.as_str().parse().ok()) {
+                    return Ok((node, addr));
+                }
+            }
+        }
+
+        if let Some(mut stderr) = node.child.stderr.take() {
+            stderr.read_to_end(&mut node.stderr)?;
+        }
+        node.child.wait()?;
+
+        Err(format!(
+            "{} exited before matching readiness pattern {:?}; stdout: {}, stderr: {}",
+            node.command,
+            ready_pattern,
+            String::from_utf8_lossy(&node.stdout),
+            String::from_utf8_lossy(&node.stderr),
+        )
+        .into())
+    }
+
+    /// Kills the supervised child and waits for it to exit
+    pub fn stop(&mut self) -> Result<()> {
+        self.child.kill()?;
+        self.child.wait()?;
+        Ok(())
+    }
+}
+
+/// Spawns a node, waits for it to report readiness, folds it into
+/// `processor`'s processed count, then stops the node
+/// # Arguments:
+/// * `processor`: The processor whose `processed_count` the node is folded into
+/// * `command`: The external command to run
+/// * `args`: Arguments passed to `command`
+/// * `ready_pattern`: Regex matched against each stdout line; its first
+///   capture group (or a named `addr` group) must parse as a `SocketAddr`
+pub fn run_node(
+    processor: &mut NodeRunnerProcessor,
+    command: &str,
+    args: &[String],
+    ready_pattern: &str,
+) -> Result<ProcessResult> {
+    let (mut node, addr) = Node::spawn_and_wait_ready(command, args, ready_pattern)?;
+    let result = processor.process(&addr.to_string());
+    if let Err(err) = node.stop() {
+        error!("Failed to stop {}: {}", command, err);
+    }
+    result
+}
@@ -0,0 +1,179 @@
+// src/server.rs
+/*
+ * JSON-RPC server mode for NodeRunner, line-delimited or Content-Length framed
+ */
+
+use crate::lossy::sanitize_lone_surrogates;
+use crate::transport::{self, Message};
+use crate::{LossyString, NodeRunnerProcessor, ProcessResult, Result};
+use log::{debug, error};
+use serde::{Deserialize, Serialize};
+use std::io::{self, BufRead, BufReader, Write};
+
+/// Selects how `serve` frames messages on stdin/stdout
+/// # Enum backing the `--framing` CLI flag
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Framing {
+    /// One JSON object per line (the original `--serve` protocol)
+    Lines,
+    /// `Content-Length` headers, the same framing LSP servers use
+    Lsp,
+}
+
+/// A single request read from stdin in `--serve` mode
+/// # Struct mirroring one line of the newline-delimited protocol
+#[derive(Debug, Deserialize)]
+struct Request {
+    /// Caller-chosen identifier echoed back on the response
+    id: serde_json::Value,
+    /// One of `"process"`, `"stats"`, or `"shutdown"`
+    method: String,
+    /// Method-specific payload
+    #[serde(default)]
+    params: serde_json::Value,
+}
+
+/// A single response written to stdout in `--serve` mode
+/// # Struct pairing a `ProcessResult` with the request's `id`
+#[derive(Debug, Serialize)]
+struct Response {
+    /// Echoed back from the originating request
+    id: serde_json::Value,
+    #[serde(flatten)]
+    result: ProcessResult,
+}
+
+/// Runs NodeRunner as a long-lived request/response loop over stdio
+/// # Reads JSON requests from stdin and writes JSON responses to stdout,
+/// # framed per `framing` (one object per line, or `Content-Length`
+/// # headers), keeping processor state alive across calls until a
+/// # `shutdown` request is received
+pub fn serve(verbose: bool, framing: Framing) -> Result<()> {
+    match framing {
+        Framing::Lines => serve_lines(verbose),
+        Framing::Lsp => serve_lsp(verbose),
+    }
+}
+
+/// Runs the newline-delimited variant of the `--serve` loop
+fn serve_lines(verbose: bool) -> Result<()> {
+    let mut processor = NodeRunnerProcessor::new(verbose);
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+
+    for line in stdin.lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let request: Request = match serde_json::from_str(&sanitize_lone_surrogates(&line)) {
+            Ok(request) => request,
+            Err(err) => {
+                error!("Failed to parse request: {}", err);
+                continue;
+            }
+        };
+
+        if verbose {
+            debug!("Dispatching method: {}", request.method);
+        }
+
+        let id = request.id.clone();
+        let method = request.method.clone();
+        let result = match dispatch(&mut processor, &request.method, request.params) {
+            Ok(result) => result,
+            Err(err) => ProcessResult {
+                success: false,
+                message: err.to_string(),
+                data: None,
+            },
+        };
+        let response = Response { id, result };
+        writeln!(out, "{}", serde_json::to_string(&response)?)?;
+        out.flush()?;
+
+        if method == "shutdown" {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Runs the `Content-Length` framed variant of the `--serve` loop, reading
+/// and writing `transport::Message`s the same way an LSP server would
+fn serve_lsp(verbose: bool) -> Result<()> {
+    let mut processor = NodeRunnerProcessor::new(verbose);
+    let stdin = io::stdin();
+    let mut reader = BufReader::new(stdin.lock());
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+
+    while let Some(message) = transport::read(&mut reader)? {
+        let (id, method, params) = match message {
+            Message::Request { id, method, params } => (id, method, params),
+            Message::Notification { method, params } => {
+                if let Err(err) = dispatch(&mut processor, &method, params) {
+                    error!("Failed to dispatch notification: {}", err);
+                }
+                continue;
+            }
+            Message::Response { .. } => continue,
+        };
+
+        if verbose {
+            debug!("Dispatching method: {}", method);
+        }
+
+        let shutting_down = method == "shutdown";
+        let result = match dispatch(&mut processor, &method, params) {
+            Ok(result) => result,
+            Err(err) => ProcessResult {
+                success: false,
+                message: err.to_string(),
+                data: None,
+            },
+        };
+        transport::write(&mut out, &Message::Response { id, result })?;
+
+        if shutting_down {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Dispatches a single request to the appropriate handler
+fn dispatch(
+    processor: &mut NodeRunnerProcessor,
+    method: &str,
+    params: serde_json::Value,
+) -> Result<ProcessResult> {
+    match method {
+        "process" => {
+            let data = match params.get("data").cloned() {
+                Some(value) => serde_json::from_value::<LossyString>(value)?,
+                None => LossyString::default(),
+            };
+            processor.process(data.as_str())
+        }
+        "stats" => Ok(ProcessResult {
+            success: true,
+            message: "Current processor statistics".to_string(),
+            data: Some(processor.get_stats()),
+        }),
+        "shutdown" => Ok(ProcessResult {
+            success: true,
+            message: "Shutting down".to_string(),
+            data: None,
+        }),
+        other => Ok(ProcessResult {
+            success: false,
+            message: format!("Unknown method: {}", other),
+            data: None,
+        }),
+    }
+}
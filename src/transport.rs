@@ -0,0 +1,138 @@
+// src/transport.rs
+/*
+ * Content-Length framed message transport, mirroring LSP-style framing
+ */
+
+use crate::lossy::sanitize_lone_surrogates;
+use crate::{ProcessResult, Result};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, Write};
+
+/// A framed message exchanged over the transport
+/// # Enum covering the three message shapes used by the `lsp` framing mode
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Message {
+    /// A call expecting a response, carrying an `id`
+    Request {
+        id: serde_json::Value,
+        method: String,
+        #[serde(default)]
+        params: serde_json::Value,
+    },
+    /// A reply to a `Request`, echoing its `id`
+    Response {
+        id: serde_json::Value,
+        #[serde(flatten)]
+        result: ProcessResult,
+    },
+    /// A one-way message with no `id` and no expected reply
+    Notification {
+        method: String,
+        #[serde(default)]
+        params: serde_json::Value,
+    },
+}
+
+/// Reads one `Content-Length` framed message from `reader`
+/// # Parses ASCII headers line-by-line until a blank line, then reads
+/// # exactly `Content-Length` bytes as the UTF-8 JSON body
+/// # Returns `Ok(None)` at EOF before any header is read
+pub fn read<R: BufRead>(reader: &mut R) -> Result<Option<Message>> {
+    let mut content_length: Option<usize> = None;
+
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some(value) = header.strip_prefix("Content-Length:") {
+            content_length = Some(value.trim().parse()?);
+        }
+    }
+
+    let content_length = content_length.ok_or("missing Content-Length header")?;
+    let mut body = vec![0u8; content_length];
+    reader.read_exact(&mut body)?;
+    let body = String::from_utf8_lossy(&body);
+    let message = serde_json::from_str(&sanitize_lone_surrogates(&body))?;
+    Ok(Some(message))
+}
+
+/// Writes `message` to `writer` as a `Content-Length` framed body, then flushes
+pub fn write<W: Write>(writer: &mut W, message: &Message) -> Result<()> {
+    let body = serde_json::to_vec(message)?;
+    write!(writer, "Content-Length: {}\r\n\r\n", body.len())?;
+    writer.write_all(&body)?;
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn write_then_read_round_trips_a_request() {
+        let sent = Message::Request {
+            id: serde_json::json!(1),
+            method: "process".to_string(),
+            params: serde_json::json!({"data": "hello"}),
+        };
+
+        let mut buf = Vec::new();
+        write(&mut buf, &sent).unwrap();
+
+        let mut reader = Cursor::new(buf);
+        let received = read(&mut reader).unwrap().unwrap();
+
+        match received {
+            Message::Request { id, method, params } => {
+                assert_eq!(id, serde_json::json!(1));
+                assert_eq!(method, "process");
+                assert_eq!(params, serde_json::json!({"data": "hello"}));
+            }
+            other => panic!("expected Message::Request, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn read_returns_none_at_eof() {
+        let mut reader = Cursor::new(Vec::new());
+        assert!(read(&mut reader).unwrap().is_none());
+    }
+
+    #[test]
+    fn read_rejects_missing_content_length_header() {
+        let mut reader = Cursor::new(b"\r\n{}".to_vec());
+        assert!(read(&mut reader).is_err());
+    }
+
+    #[test]
+    fn read_handles_multiple_messages_back_to_back() {
+        let first = Message::Notification {
+            method: "ping".to_string(),
+            params: serde_json::Value::Null,
+        };
+        let second = Message::Notification {
+            method: "pong".to_string(),
+            params: serde_json::Value::Null,
+        };
+
+        let mut buf = Vec::new();
+        write(&mut buf, &first).unwrap();
+        write(&mut buf, &second).unwrap();
+
+        let mut reader = Cursor::new(buf);
+        let first_read = read(&mut reader).unwrap().unwrap();
+        let second_read = read(&mut reader).unwrap().unwrap();
+
+        assert!(matches!(first_read, Message::Notification { method, .. } if method == "ping"));
+        assert!(matches!(second_read, Message::Notification { method, .. } if method == "pong"));
+    }
+}